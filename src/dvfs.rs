@@ -0,0 +1,254 @@
+//! Reads the GPU's DVFS (dynamic voltage/frequency scaling) operating-point
+//! table from the IORegistry, so performance-state residencies can be turned
+//! into an estimated clock speed.
+
+use core_foundation::{
+    base::{CFType, CFTypeRef, TCFType},
+    data::CFData,
+    dictionary::CFDictionaryRef,
+    string::CFString,
+};
+use std::ffi::CString;
+use std::fmt;
+use std::os::raw::c_void;
+use std::ptr::null;
+
+/// A single DVFS operating point: the GPU clock (in MHz) and supply voltage
+/// (in millivolts) for one performance state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DvfsPoint {
+    /// Operating frequency, in MHz.
+    pub freq_mhz: f64,
+    /// Supply voltage, in millivolts.
+    pub voltage_mv: u32,
+}
+
+/// An ordered table of [`DvfsPoint`]s decoded from the `IOGPU`/`pmgr`
+/// IORegistry node, indexed the same way the GPU performance-state channel
+/// indexes its (non-idle) states: table index 0 is the first real operating
+/// point, `P1`.
+#[derive(Debug, Default, Clone)]
+pub struct DvfsTable {
+    points: Vec<DvfsPoint>,
+}
+
+/// Errors that can occur while reading or decoding the DVFS table.
+#[derive(Debug)]
+pub enum DvfsError {
+    /// The `IOGPU`/`pmgr` service could not be found in the IORegistry.
+    ServiceNotFound,
+    /// Neither `gpu-perf-states` nor `voltage-states` was present on the service.
+    PropertyNotFound,
+    /// The property was present but not a well-formed packed array of
+    /// little-endian `(frequency, voltage)` pairs.
+    MalformedProperty,
+}
+
+impl fmt::Display for DvfsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DvfsError::ServiceNotFound => write!(f, "IOGPU/pmgr service not found in IORegistry"),
+            DvfsError::PropertyNotFound => {
+                write!(f, "gpu-perf-states/voltage-states property not found")
+            }
+            DvfsError::MalformedProperty => write!(f, "DVFS table property was malformed"),
+        }
+    }
+}
+
+impl std::error::Error for DvfsError {}
+
+// Each entry is two little-endian u32s: (frequency in kHz, voltage in mV).
+const ENTRY_SIZE: usize = 8;
+
+// The IORegistry properties known to carry the DVFS table across hardware
+// generations; tried in order.
+const PROPERTY_NAMES: &[&str] = &["gpu-perf-states", "voltage-states"];
+
+impl From<Vec<DvfsPoint>> for DvfsTable {
+    fn from(points: Vec<DvfsPoint>) -> Self {
+        Self { points }
+    }
+}
+
+impl DvfsTable {
+    /// Reads the DVFS table from the `IOGPU` (falling back to `pmgr`) node in
+    /// the IORegistry at startup.
+    pub fn from_registry() -> Result<Self, DvfsError> {
+        let mut last_err = DvfsError::ServiceNotFound;
+        for service_name in ["IOGPU", "pmgr"] {
+            if let Ok(service) = unsafe { find_service(service_name) } {
+                let result = Self::from_service(service);
+                unsafe {
+                    IOObjectRelease(service);
+                }
+                match result {
+                    Ok(table) => return Ok(table),
+                    Err(err) => last_err = err,
+                }
+            }
+        }
+
+        Err(last_err)
+    }
+
+    fn from_service(service: IOServiceRef) -> Result<Self, DvfsError> {
+        for property_name in PROPERTY_NAMES {
+            if let Some(data) = unsafe { search_property(service, property_name) } {
+                return Self::decode(&data);
+            }
+        }
+
+        Err(DvfsError::PropertyNotFound)
+    }
+
+    /// Decodes a packed little-endian `(freq_khz, voltage_mv)` byte blob into
+    /// an ordered table of [`DvfsPoint`]s.
+    fn decode(data: &[u8]) -> Result<Self, DvfsError> {
+        if data.is_empty() || data.len() % ENTRY_SIZE != 0 {
+            return Err(DvfsError::MalformedProperty);
+        }
+
+        let points = data
+            .chunks_exact(ENTRY_SIZE)
+            .map(|entry| {
+                let freq_khz = u32::from_le_bytes(entry[0..4].try_into().unwrap());
+                let voltage_mv = u32::from_le_bytes(entry[4..8].try_into().unwrap());
+                DvfsPoint {
+                    freq_mhz: freq_khz as f64 / 1000.0,
+                    voltage_mv,
+                }
+            })
+            .collect();
+
+        Ok(Self { points })
+    }
+
+    /// The operating point at `index` (0 = `P1`), or `None` if `index` is out
+    /// of range for this hardware's table.
+    pub fn get(&self, index: usize) -> Option<DvfsPoint> {
+        self.points.get(index).copied()
+    }
+
+    /// The number of operating points in the table.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether the table has no operating points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+// Opaque IOKit object/service handles (`io_object_t` / `io_registry_entry_t`
+// are both typedef'd to `mach_port_t` in IOKit headers).
+type IOServiceRef = u32;
+type KernReturn = i32;
+
+unsafe fn find_service(name: &str) -> Result<IOServiceRef, DvfsError> {
+    let name_c = CString::new(name).map_err(|_| DvfsError::ServiceNotFound)?;
+    let matching = IOServiceMatching(name_c.as_ptr());
+    if matching.is_null() {
+        return Err(DvfsError::ServiceNotFound);
+    }
+
+    // IOServiceGetMatchingService consumes (releases) the matching dictionary.
+    let service = IOServiceGetMatchingService(kIOMasterPortDefault, matching);
+    if service == 0 {
+        return Err(DvfsError::ServiceNotFound);
+    }
+
+    Ok(service)
+}
+
+unsafe fn search_property(service: IOServiceRef, key: &str) -> Option<Vec<u8>> {
+    let key_cf = CFString::new(key);
+    let plane = CString::new("IOService").ok()?;
+
+    let prop_ref = IORegistryEntrySearchCFProperty(
+        service,
+        plane.as_ptr(),
+        key_cf.as_concrete_TypeRef(),
+        null(),
+        K_IOREGISTRY_ITERATE_RECURSIVELY | K_IOREGISTRY_ITERATE_PARENTS,
+    );
+
+    if prop_ref.is_null() {
+        return None;
+    }
+
+    let value: CFType = CFType::wrap_under_create_rule(prop_ref);
+    let data = value.downcast::<CFData>()?;
+    Some(data.bytes().to_vec())
+}
+
+// IOKit's `kIORegistryIterateRecursively`/`kIORegistryIterateParents` option flags.
+const K_IOREGISTRY_ITERATE_RECURSIVELY: u32 = 1 << 0;
+const K_IOREGISTRY_ITERATE_PARENTS: u32 = 1 << 1;
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    static kIOMasterPortDefault: u32;
+
+    fn IOServiceMatching(name: *const i8) -> CFDictionaryRef;
+
+    fn IOServiceGetMatchingService(master_port: u32, matching: CFDictionaryRef) -> IOServiceRef;
+
+    fn IORegistryEntrySearchCFProperty(
+        entry: IOServiceRef,
+        plane: *const i8,
+        key: core_foundation::string::CFStringRef,
+        allocator: *const c_void,
+        options: u32,
+    ) -> CFTypeRef;
+
+    fn IOObjectRelease(object: IOServiceRef) -> KernReturn;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_multi_entry() {
+        let data: Vec<u8> = [(396_000u32, 650u32), (720_000, 700), (1_278_000, 850)]
+            .iter()
+            .flat_map(|(freq_khz, voltage_mv)| {
+                freq_khz
+                    .to_le_bytes()
+                    .into_iter()
+                    .chain(voltage_mv.to_le_bytes())
+            })
+            .collect();
+
+        let table = DvfsTable::decode(&data).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(
+            table.get(0),
+            Some(DvfsPoint { freq_mhz: 396.0, voltage_mv: 650 })
+        );
+        assert_eq!(
+            table.get(2),
+            Some(DvfsPoint { freq_mhz: 1278.0, voltage_mv: 850 })
+        );
+        assert_eq!(table.get(3), None);
+    }
+
+    #[test]
+    fn test_decode_empty_is_malformed() {
+        assert!(matches!(
+            DvfsTable::decode(&[]),
+            Err(DvfsError::MalformedProperty)
+        ));
+    }
+
+    #[test]
+    fn test_decode_length_not_multiple_of_entry_size_is_malformed() {
+        let data = vec![0u8; ENTRY_SIZE + 1];
+        assert!(matches!(
+            DvfsTable::decode(&data),
+            Err(DvfsError::MalformedProperty)
+        ));
+    }
+}
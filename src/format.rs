@@ -0,0 +1,3 @@
+//! Output formatters that render GPU readings in alternative text formats.
+
+pub mod prometheus;
@@ -0,0 +1,101 @@
+//! Renders GPU readings as OpenMetrics/Prometheus text exposition format,
+//! so `siligpu` can be scraped directly or used as a node-exporter textfile
+//! collector.
+
+use crate::ioreport::GPUChannel;
+use std::fmt::Write as _;
+
+/// Renders a slice of [`GPUChannel`] readings as OpenMetrics/Prometheus text
+/// exposition format.
+///
+/// Every value here is a point-in-time reading over the sampling interval
+/// rather than a monotonically increasing total, so all metrics are
+/// exposed as gauges even where the name might suggest a counter.
+pub fn render(channels: &[GPUChannel]) -> String {
+    let mut out = String::new();
+
+    write_metric_header(
+        &mut out,
+        "siligpu_usage_percent",
+        "Percentage of time the GPU was active over the sampling interval",
+    );
+    for channel in channels {
+        writeln!(
+            out,
+            "siligpu_usage_percent{{group=\"{}\",subgroup=\"{}\"}} {}",
+            escape_label(&channel.group),
+            escape_label(&channel.subgroup),
+            channel.usage()
+        )
+        .unwrap();
+    }
+
+    write_metric_header(
+        &mut out,
+        "siligpu_active_residency_microseconds",
+        "Time spent in active (non-idle) GPU performance states over the sampling interval",
+    );
+    for channel in channels {
+        writeln!(
+            out,
+            "siligpu_active_residency_microseconds{{group=\"{}\",subgroup=\"{}\"}} {}",
+            escape_label(&channel.group),
+            escape_label(&channel.subgroup),
+            channel.active_residency()
+        )
+        .unwrap();
+    }
+
+    write_metric_header(
+        &mut out,
+        "siligpu_total_residency_microseconds",
+        "Total time covered by the sampling interval across all GPU performance states",
+    );
+    for channel in channels {
+        writeln!(
+            out,
+            "siligpu_total_residency_microseconds{{group=\"{}\",subgroup=\"{}\"}} {}",
+            escape_label(&channel.group),
+            escape_label(&channel.subgroup),
+            channel.total_residency()
+        )
+        .unwrap();
+    }
+
+    write_metric_header(
+        &mut out,
+        "siligpu_state_residency_microseconds",
+        "Time spent in each individual GPU performance state over the sampling interval",
+    );
+    for channel in channels {
+        for state in &channel.states {
+            writeln!(
+                out,
+                "siligpu_state_residency_microseconds{{group=\"{}\",subgroup=\"{}\",state=\"{}\"}} {}",
+                escape_label(&channel.group),
+                escape_label(&channel.subgroup),
+                escape_label(&state.name),
+                state.residency
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+/// Writes the `# HELP`/`# TYPE` annotation pair that must precede a metric's
+/// samples in OpenMetrics text exposition format.
+fn write_metric_header(out: &mut String, name: &str, help: &str) {
+    writeln!(out, "# HELP {name} {help}").unwrap();
+    writeln!(out, "# TYPE {name} gauge").unwrap();
+}
+
+/// Escapes characters illegal inside an OpenMetrics/Prometheus label value:
+/// backslash, double quote, and newline.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,3 +1,4 @@
+use crate::dvfs::DvfsTable;
 use core_foundation::{
     array::{CFArray, CFArrayRef},
     base::{CFType, CFTypeRef, TCFType},
@@ -6,7 +7,7 @@ use core_foundation::{
 };
 use core_foundation_sys::base::CFRelease;
 use serde::Serialize;
-use std::{fmt, os::raw::c_void, ptr::null};
+use std::{fmt, os::raw::c_void, ptr::null, time::Duration};
 
 /// Represents a single GPU performance state (e.g., "P1", "IDLE").
 #[derive(Debug, Serialize)]
@@ -54,6 +55,99 @@ impl GPUChannel {
             (self.active_residency() as f64 / total as f64) * 100.0
         }
     }
+
+    /// Estimates the time-weighted average GPU clock, in MHz, over this
+    /// delta: `sum(residency_i * freq_i) / sum(residency_i)` across the
+    /// active (non-IDLE/OFF/DOWN) states, using `dvfs` to map each state to
+    /// its operating frequency.
+    ///
+    /// Returns `NaN` if there are no active states, or if this channel's
+    /// state count and `dvfs`'s table length disagree (which can happen
+    /// across hardware generations) such that a state has no matching entry.
+    pub fn effective_frequency_mhz(&self, dvfs: &DvfsTable) -> f64 {
+        let mut weighted_sum = 0.0;
+        let mut total_residency = 0i64;
+        let mut point_index = 0usize;
+
+        for state in &self.states {
+            if !state.is_active {
+                continue;
+            }
+
+            let Some(point) = dvfs.get(point_index) else {
+                return f64::NAN;
+            };
+            point_index += 1;
+
+            weighted_sum += state.residency as f64 * point.freq_mhz;
+            total_residency += state.residency;
+        }
+
+        if total_residency == 0 {
+            f64::NAN
+        } else {
+            weighted_sum / total_residency as f64
+        }
+    }
+}
+
+/// Represents a scalar energy/power channel (e.g. "Energy Model" / "GPU Energy").
+///
+/// Unlike [`GPUChannel`], these channels carry a single accumulated counter
+/// rather than a set of named states.
+#[derive(Debug, Serialize)]
+pub struct EnergyChannel {
+    /// The group name (e.g., "Energy Model").
+    pub group: String,
+    /// The subgroup name (e.g., "GPU Energy").
+    pub subgroup: String,
+    /// The name of the individual channel within the group/subgroup.
+    pub channel_name: String,
+    /// The energy consumed over the sampling delta, in microjoules.
+    pub energy_uj: i64,
+}
+
+impl EnergyChannel {
+    /// Computes the average power draw over `elapsed`, in milliwatts.
+    ///
+    /// Returns `0.0` if `elapsed` is zero rather than dividing by zero.
+    pub fn average_milliwatts(&self, elapsed: Duration) -> f64 {
+        let seconds = elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            0.0
+        } else {
+            (self.energy_uj as f64 / seconds) / 1000.0
+        }
+    }
+}
+
+/// A single IOReport channel reading: either performance-state residencies
+/// or a scalar energy/power counter.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Channel {
+    /// A performance-state residency channel (e.g. GPU P-states).
+    Performance(GPUChannel),
+    /// A scalar energy/power channel (e.g. GPU energy consumption).
+    Energy(EnergyChannel),
+}
+
+impl Channel {
+    /// The IOReport group name, regardless of channel kind.
+    pub fn group(&self) -> &str {
+        match self {
+            Channel::Performance(c) => &c.group,
+            Channel::Energy(c) => &c.group,
+        }
+    }
+
+    /// The IOReport subgroup name, regardless of channel kind.
+    pub fn subgroup(&self) -> &str {
+        match self {
+            Channel::Performance(c) => &c.subgroup,
+            Channel::Energy(c) => &c.subgroup,
+        }
+    }
 }
 
 /// A wrapper around the IOReport library for querying system statistics.
@@ -76,10 +170,14 @@ impl fmt::Display for IOReportError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             IOReportError::ChannelsUnavailable => write!(f, "IOReport channels unavailable"),
-            IOReportError::SubscriptionFailed => write!(f, "Failed to create IOReport subscription"),
+            IOReportError::SubscriptionFailed => {
+                write!(f, "Failed to create IOReport subscription")
+            }
             IOReportError::SampleFailed => write!(f, "Failed to capture IOReport sample"),
             IOReportError::DeltaFailed => write!(f, "Failed to compute IOReport sample delta"),
-            IOReportError::MissingChannelArray => write!(f, "IOReport response missing channel data"),
+            IOReportError::MissingChannelArray => {
+                write!(f, "IOReport response missing channel data")
+            }
         }
     }
 }
@@ -89,28 +187,59 @@ impl std::error::Error for IOReportError {}
 // Opaque type for the subscription
 type IOReportSubscriptionRef = *const c_void;
 
+// IOReport channel "format" tag, read via `IOReportChannelGetFormat`. These
+// mirror the `kIOReportFormat*` constants used by the private IOReport API.
+const IOREPORT_FORMAT_SIMPLE: i32 = 1;
+const IOREPORT_FORMAT_STATE: i32 = 2;
+
 impl IOReport {
-    /// Creates a new subscription for the requested IOReport group and subgroup.
-    pub fn new(group_name: &str, subgroup_name: &str) -> Result<Self, IOReportError> {
-        let group_cf = CFString::new(group_name);
-        let subgroup_cf = CFString::new(subgroup_name);
-
-        let chans_raw = unsafe {
-            IOReportCopyChannelsInGroup(
-                group_cf.as_concrete_TypeRef(),
-                subgroup_cf.as_concrete_TypeRef(),
-                0,
-                0,
-                0,
-            )
-        };
+    /// Creates a new subscription covering one or more `(group, subgroup)`
+    /// channel groups, merging them into a single subscription. This is how
+    /// `siligpu` subscribes to both the GPU performance-state group and
+    /// scalar power/energy groups (e.g. `"Energy Model"`) at once.
+    pub fn new(groups: &[(&str, &str)]) -> Result<Self, IOReportError> {
+        let mut merged: Option<CFDictionary<CFString, CFType>> = None;
+
+        for (group_name, subgroup_name) in groups {
+            let group_cf = CFString::new(group_name);
+            let subgroup_cf = CFString::new(subgroup_name);
+
+            let chans_raw = unsafe {
+                IOReportCopyChannelsInGroup(
+                    group_cf.as_concrete_TypeRef(),
+                    subgroup_cf.as_concrete_TypeRef(),
+                    0,
+                    0,
+                    0,
+                )
+            };
+
+            if chans_raw.is_null() {
+                continue;
+            }
 
-        if chans_raw.is_null() {
-            return Err(IOReportError::ChannelsUnavailable);
+            let chans: CFDictionary<CFString, CFType> =
+                unsafe { CFDictionary::wrap_under_create_rule(chans_raw) };
+
+            merged = Some(match merged {
+                None => chans,
+                Some(existing) => unsafe {
+                    let merged_raw = IOReportMergeChannels(
+                        existing.as_concrete_TypeRef(),
+                        chans.as_concrete_TypeRef(),
+                        null(),
+                    );
+
+                    if merged_raw.is_null() {
+                        existing
+                    } else {
+                        CFDictionary::wrap_under_create_rule(merged_raw)
+                    }
+                },
+            });
         }
 
-        let channels: CFDictionary<CFString, CFType> =
-            unsafe { CFDictionary::wrap_under_create_rule(chans_raw) };
+        let channels = merged.ok_or(IOReportError::ChannelsUnavailable)?;
 
         let mut sub_ref: CFDictionaryRef = null();
         let subscription = unsafe {
@@ -150,10 +279,19 @@ impl IOReport {
         }
     }
 
+    /// Computes the delta between two samples and decodes it into typed
+    /// [`Channel`]s, branching on each channel's format: performance-state
+    /// channels decode per-state residencies, while scalar energy/power
+    /// channels read a single integer counter (the energy used between the
+    /// two samples). Each channel keeps its group/subgroup for callers to
+    /// filter on. `elapsed` is not used here; the raw energy counter is
+    /// converted to average milliwatts by
+    /// [`EnergyChannel::average_milliwatts`].
     pub fn get_delta(
         sample1: &CFDictionary<CFString, CFType>,
         sample2: &CFDictionary<CFString, CFType>,
-    ) -> Result<Vec<GPUChannel>, IOReportError> {
+        elapsed: Duration,
+    ) -> Result<Vec<Channel>, IOReportError> {
         let delta_raw = unsafe {
             IOReportCreateSamplesDelta(
                 sample1.as_concrete_TypeRef(),
@@ -196,10 +334,41 @@ impl IOReport {
             }
             .to_string();
             let subgrp_name = unsafe {
-                CFString::wrap_under_get_rule(IOReportChannelGetSubGroup(dict.as_concrete_TypeRef()))
+                CFString::wrap_under_get_rule(IOReportChannelGetSubGroup(
+                    dict.as_concrete_TypeRef(),
+                ))
             }
             .to_string();
 
+            let format = unsafe { IOReportChannelGetFormat(dict.as_concrete_TypeRef()) };
+
+            if format == IOREPORT_FORMAT_SIMPLE {
+                let channel_name = unsafe {
+                    CFString::wrap_under_get_rule(IOReportChannelGetChannelName(
+                        dict.as_concrete_TypeRef(),
+                    ))
+                }
+                .to_string();
+                let energy_uj =
+                    unsafe { IOReportSimpleGetIntegerValue(dict.as_concrete_TypeRef(), null()) };
+
+                results.push(Channel::Energy(EnergyChannel {
+                    group: grp_name,
+                    subgroup: subgrp_name,
+                    channel_name,
+                    energy_uj,
+                }));
+
+                continue;
+            }
+
+            if format != IOREPORT_FORMAT_STATE {
+                // Not a shape we know how to decode (e.g. a histogram
+                // channel); skip rather than misreading it as state
+                // residency.
+                continue;
+            }
+
             let state_count = unsafe { IOReportStateGetCount(dict.as_concrete_TypeRef()) };
             let mut states = Vec::new();
 
@@ -225,11 +394,11 @@ impl IOReport {
                 });
             }
 
-            results.push(GPUChannel {
+            results.push(Channel::Performance(GPUChannel {
                 group: grp_name,
                 subgroup: subgrp_name,
                 states,
-            });
+            }));
         }
 
         Ok(results)
@@ -256,6 +425,12 @@ extern "C" {
         b: u64,
     ) -> CFDictionaryRef;
 
+    fn IOReportMergeChannels(
+        a: CFDictionaryRef,
+        b: CFDictionaryRef,
+        nil: CFTypeRef,
+    ) -> CFDictionaryRef;
+
     fn IOReportCreateSubscription(
         allocator: *const c_void,
         channels: CFDictionaryRef,
@@ -278,8 +453,12 @@ extern "C" {
 
     fn IOReportChannelGetGroup(item: CFDictionaryRef) -> CFStringRef;
     fn IOReportChannelGetSubGroup(item: CFDictionaryRef) -> CFStringRef;
+    fn IOReportChannelGetChannelName(item: CFDictionaryRef) -> CFStringRef;
+    fn IOReportChannelGetFormat(item: CFDictionaryRef) -> i32;
 
     fn IOReportStateGetCount(item: CFDictionaryRef) -> i32;
     fn IOReportStateGetNameForIndex(item: CFDictionaryRef, index: i32) -> CFStringRef;
     fn IOReportStateGetResidency(item: CFDictionaryRef, index: i32) -> i64;
-}
\ No newline at end of file
+
+    fn IOReportSimpleGetIntegerValue(item: CFDictionaryRef, unknown: CFTypeRef) -> i64;
+}
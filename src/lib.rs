@@ -1,4 +1,8 @@
+pub mod dvfs;
+pub mod format;
 pub mod ioreport;
+pub mod monitor;
+pub mod stats;
 
 use std::{fmt, time::Duration};
 
@@ -7,10 +11,15 @@ use std::{fmt, time::Duration};
 pub enum ParseDurationError {
     /// The string was empty or whitespace.
     Empty,
-    /// The numeric portion could not be parsed.
+    /// A segment's numeric portion could not be parsed, or segments were
+    /// not given in strictly decreasing unit order (e.g. `1s1h`, or a
+    /// repeated unit like `1s2s`).
     InvalidNumber,
     /// The unit was not recognized (e.g., `1x`).
     UnsupportedUnit(String),
+    /// A number in a compound expression had no unit following it (e.g.
+    /// `1m30`). A single bare number on its own still means milliseconds.
+    TrailingNumber,
 }
 
 impl fmt::Display for ParseDurationError {
@@ -21,14 +30,34 @@ impl fmt::Display for ParseDurationError {
             ParseDurationError::UnsupportedUnit(unit) => {
                 write!(f, "Unsupported duration unit: {unit}")
             }
+            ParseDurationError::TrailingNumber => {
+                write!(f, "Trailing number with no unit in duration")
+            }
         }
     }
 }
 
 impl std::error::Error for ParseDurationError {}
 
-/// Parse strings like "100", "100ms", "1s", "1m", "1h" into a `Duration`.
-/// Accepts upper- or lower-case units and trims surrounding whitespace.
+// Unit scale (in milliseconds) and a rank used to enforce that compound
+// expressions list units in decreasing magnitude, largest first.
+fn unit_scale_and_rank(unit: &str) -> Option<(f64, u8)> {
+    match unit {
+        "ms" => Some((1.0, 0)),
+        "s" => Some((1_000.0, 1)),
+        "m" => Some((60_000.0, 2)),
+        "h" => Some((3_600_000.0, 3)),
+        "d" => Some((86_400_000.0, 4)),
+        _ => None,
+    }
+}
+
+/// Parse strings like "100", "100ms", "1s", "1m", "1h", "1d" into a
+/// `Duration`. Also accepts fractional values ("0.5s", "2.5m") and compound
+/// expressions that chain multiple units in decreasing magnitude order
+/// ("1h15m", "1m30s"). Accepts upper- or lower-case units and trims
+/// surrounding whitespace. A bare number on its own (no unit) means
+/// milliseconds, as before.
 pub fn parse_duration(s: &str) -> Result<Duration, ParseDurationError> {
     let s = s.trim();
     if s.is_empty() {
@@ -36,22 +65,53 @@ pub fn parse_duration(s: &str) -> Result<Duration, ParseDurationError> {
     }
 
     let normalized = s.to_ascii_lowercase();
+    let bytes = normalized.as_bytes();
+
+    let mut total_ms = 0.0f64;
+    let mut last_rank: Option<u8> = None;
+    let mut segments = 0u32;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let num_start = pos;
+        while pos < bytes.len() && (bytes[pos].is_ascii_digit() || bytes[pos] == b'.') {
+            pos += 1;
+        }
+        if pos == num_start {
+            return Err(ParseDurationError::InvalidNumber);
+        }
+        let value: f64 = normalized[num_start..pos]
+            .parse()
+            .map_err(|_| ParseDurationError::InvalidNumber)?;
 
-    let parse_num = |num: &str| num.parse::<u64>().map_err(|_| ParseDurationError::InvalidNumber);
-
-    if let Some(num) = normalized.strip_suffix("ms") {
-        Ok(Duration::from_millis(parse_num(num)?))
-    } else if let Some(num) = normalized.strip_suffix('s') {
-        Ok(Duration::from_secs(parse_num(num)?))
-    } else if let Some(num) = normalized.strip_suffix('m') {
-        Ok(Duration::from_secs(parse_num(num)? * 60))
-    } else if let Some(num) = normalized.strip_suffix('h') {
-        Ok(Duration::from_secs(parse_num(num)? * 3600))
-    } else {
-        if normalized.chars().any(|c| c.is_ascii_alphabetic()) {
-            return Err(ParseDurationError::UnsupportedUnit(normalized));
+        let unit_start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_alphabetic() {
+            pos += 1;
         }
+        let unit = &normalized[unit_start..pos];
 
-        Ok(Duration::from_millis(parse_num(&normalized)?))
+        if unit.is_empty() {
+            // A bare number is only valid as the entire, sole expression
+            // (meaning milliseconds); anywhere else it's a dangling number.
+            if segments == 0 && pos == bytes.len() {
+                return Ok(Duration::from_secs_f64(value / 1000.0));
+            }
+            return Err(ParseDurationError::TrailingNumber);
+        }
+
+        let (scale_ms, rank) = unit_scale_and_rank(unit)
+            .ok_or_else(|| ParseDurationError::UnsupportedUnit(unit.to_string()))?;
+
+        if let Some(last_rank) = last_rank {
+            if rank >= last_rank {
+                return Err(ParseDurationError::InvalidNumber);
+            }
+        }
+        last_rank = Some(rank);
+
+        total_ms += value * scale_ms;
+        segments += 1;
     }
+
+    Ok(Duration::from_secs_f64(total_ms / 1000.0))
 }
@@ -1,10 +1,23 @@
 use anyhow::{Context, Result};
 use clap::{ArgGroup, Parser};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use siligpu::dvfs::DvfsTable;
+use siligpu::format::prometheus;
+use siligpu::ioreport::{Channel, GPUChannel, IOReport};
+use siligpu::monitor::MonitorService;
 use siligpu::parse_duration;
-use siligpu::ioreport::IOReport;
+use siligpu::stats::UsageStats;
+
+/// The IOReport `(group, subgroup)` pairs siligpu subscribes to: GPU
+/// performance-state residencies and GPU energy/power consumption.
+const SUBSCRIBED_GROUPS: &[(&str, &str)] = &[
+    ("GPU Stats", "GPU Performance States"),
+    ("Energy Model", "GPU Energy"),
+];
 
 #[derive(Parser)]
 #[command(
@@ -14,7 +27,7 @@ use siligpu::ioreport::IOReport;
 )]
 #[command(group(
     ArgGroup::new("mode")
-        .args(&["verbose", "summary", "value_only", "json"])
+        .args(&["verbose", "summary", "value_only", "json", "prometheus"])
         .multiple(false),
 ))]
 struct Args {
@@ -34,82 +47,225 @@ struct Args {
     #[arg(short = 'j', long = "json")]
     json: bool,
 
+    /// Prometheus/OpenMetrics mode – output results in text exposition format
+    #[arg(short = 'p', long = "prometheus")]
+    prometheus: bool,
+
     /// Time between samples
-    /// Accepts plain numbers (ms) or units: ms, s, m, h. (e.g. `100`, `100ms`, `1s`, `1m`, `1h`).
+    /// Accepts plain numbers (ms), units: ms, s, m, h, d, fractional values
+    /// (e.g. `0.5s`), and compound expressions (e.g. `1m30s`, `1h15m`).
     #[arg(short = 't', long = "time", default_value = "1000ms", value_parser = parse_duration)]
     time: Duration,
+
+    /// Watch mode – keep sampling every `--time` interval until stopped (Ctrl-C)
+    /// or until `--count` readings have been taken.
+    #[arg(short = 'w', long = "watch")]
+    watch: bool,
+
+    /// Number of readings to take in watch mode before exiting. Implies `--watch`.
+    #[arg(short = 'n', long = "count")]
+    count: Option<u64>,
 }
 // `parse_duration` is provided by the library crate (see `src/lib.rs`).
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let report = IOReport::new("GPU Stats", "GPU Performance States")
+    let report = IOReport::new(SUBSCRIBED_GROUPS)
         .map_err(|e| anyhow::anyhow!(e))
         .context("Failed to initialize IOReport. Are you running on an Apple Silicon Mac?")?;
 
+    // The DVFS table is best-effort: older hardware/macOS combinations may
+    // not expose it, in which case we simply omit the frequency estimate.
+    let dvfs = DvfsTable::from_registry().ok();
+
+    if args.watch || args.count.is_some() {
+        return run_watch(&args, report, dvfs.as_ref());
+    }
+
     let sample1 = report
         .sample()
         .context("Failed to capture initial IOReport sample")?;
+    let start = Instant::now();
     sleep(args.time);
     let sample2 = report
         .sample()
         .context("Failed to capture second IOReport sample")?;
 
-    let channels = IOReport::get_delta(&sample1, &sample2)
+    let channels = IOReport::get_delta(&sample1, &sample2, start.elapsed())
         .context("Failed to compute delta between IOReport samples")?;
 
     if channels.is_empty() {
         anyhow::bail!("No GPU channels found. This tool requires an Apple Silicon Mac.");
     }
 
+    let power = find_gpu_power(&channels, start.elapsed());
     let mut printed = false;
 
-    for channel in channels {
-        if channel.group != "GPU Stats" || channel.subgroup != "GPU Performance States" {
+    for channel in &channels {
+        let Channel::Performance(channel) = channel else {
+            continue;
+        };
+        if !is_gpu_perf_channel(channel) {
             continue;
         }
 
         printed = true;
+        print_channel(channel, &args, None, power, dvfs.as_ref())?;
+    }
 
-        let usage = channel.usage();
-
-        if args.json {
-            let json_output = serde_json::json!({
-                "usage_percentage": usage,
-                "total_active_us": channel.active_residency(),
-                "total_time_us": channel.total_residency(),
-                "states": channel.states
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output)?);
-        } else if args.value_only {
-            println!("{:.2}%", usage);
-        } else if args.summary {
-            println!("Usage: {:>6.2}%", usage);
-        } else {
-            // Verbose (default)
-            println!("{:>0} / {:<0}", channel.group, channel.subgroup);
-            for state in &channel.states {
-                println!("  {:>6}: {:>21} µs", state.name, state.residency);
+    if !printed {
+        anyhow::bail!(
+            "No GPU performance states matched. This may occur on unsupported hardware or macOS versions."
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs the continuous `--watch` / `--count` mode: spawns a [`MonitorService`]
+/// and prints one reading per tick until the user interrupts (Ctrl-C) or the
+/// requested `--count` of readings has been printed.
+fn run_watch(args: &Args, report: IOReport, dvfs: Option<&DvfsTable>) -> Result<()> {
+    let running = Arc::new(AtomicBool::new(true));
+    let running_handler = Arc::clone(&running);
+    ctrlc::set_handler(move || running_handler.store(false, Ordering::Relaxed))
+        .context("Failed to install Ctrl-C handler")?;
+
+    let (service, rx) = MonitorService::start(report, args.time);
+
+    let mut emitted = 0u64;
+    let mut stats = UsageStats::new();
+
+    while running.load(Ordering::Relaxed) {
+        if let Some(limit) = args.count {
+            if emitted >= limit {
+                break;
             }
+        }
+
+        let (channels, elapsed) = match rx.recv_timeout(Duration::from_millis(500)) {
+            Ok(reading) => reading.context("Failed to compute delta between IOReport samples")?,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+
+        let power = find_gpu_power(&channels, elapsed);
+
+        for channel in &channels {
+            let Channel::Performance(channel) = channel else {
+                continue;
+            };
+            if !is_gpu_perf_channel(channel) {
+                continue;
+            }
+            stats.record(channel);
+            print_channel(channel, args, Some(&stats), power, dvfs)?;
+        }
+
+        emitted += 1;
+    }
+
+    service.join();
+
+    if !args.json && !args.prometheus && !args.value_only {
+        if let Some(usage) = stats.usage() {
             println!(
-                "  {:>15}: {:>12} µs (active)",
-                "→ Total active",
-                channel.active_residency()
-            );
-            println!(
-                "  {:>15}: {:>12} µs (total)",
-                "→ Total",
-                channel.total_residency()
+                "usage min/avg/max = {:.1}/{:.1}/{:.1}%",
+                usage.min(),
+                usage.mean(),
+                usage.max()
             );
-            println!("  {:>15}: {:>12.2} %", "→ Usage", usage);
         }
     }
 
-    if !printed {
-        anyhow::bail!(
-            "No GPU performance states matched. This may occur on unsupported hardware or macOS versions."
+    Ok(())
+}
+
+/// Whether a channel is the GPU performance-state channel this tool reports on.
+fn is_gpu_perf_channel(channel: &GPUChannel) -> bool {
+    channel.group == "GPU Stats" && channel.subgroup == "GPU Performance States"
+}
+
+/// Finds the GPU energy channel among a delta's channels and converts it to
+/// average milliwatts over `elapsed`, if present on this hardware.
+fn find_gpu_power(channels: &[Channel], elapsed: Duration) -> Option<f64> {
+    channels.iter().find_map(|channel| match channel {
+        Channel::Energy(energy)
+            if energy.group == "Energy Model" && energy.subgroup == "GPU Energy" =>
+        {
+            Some(energy.average_milliwatts(elapsed))
+        }
+        _ => None,
+    })
+}
+
+/// Prints a single channel's reading in whichever of the five output modes
+/// was requested, shared by both the one-shot and `--watch` code paths.
+/// When `stats` is given (periodic `--watch` ticks), JSON mode rolls the
+/// accumulated [`UsageStats`] into the output alongside the latest `states`.
+/// `power_mw`, when available, is the GPU's average power draw over the delta.
+/// `dvfs`, when available, lets verbose/JSON output include an estimated
+/// effective clock speed (see [`GPUChannel::effective_frequency_mhz`]).
+fn print_channel(
+    channel: &GPUChannel,
+    args: &Args,
+    stats: Option<&UsageStats>,
+    power_mw: Option<f64>,
+    dvfs: Option<&DvfsTable>,
+) -> Result<()> {
+    let usage = channel.usage();
+    let freq_mhz = dvfs.map(|dvfs| channel.effective_frequency_mhz(dvfs));
+
+    if args.json {
+        let mut json_output = serde_json::json!({
+            "usage_percentage": usage,
+            "total_active_us": channel.active_residency(),
+            "total_time_us": channel.total_residency(),
+            "states": channel.states
+        });
+        if let Some(power_mw) = power_mw {
+            json_output["power_milliwatts"] = serde_json::json!(power_mw);
+        }
+        if let Some(freq_mhz) = freq_mhz.filter(|f| !f.is_nan()) {
+            json_output["effective_frequency_mhz"] = serde_json::json!(freq_mhz);
+        }
+        if let Some(stats) = stats {
+            json_output["aggregate"] = serde_json::to_value(stats)?;
+        }
+        println!("{}", serde_json::to_string_pretty(&json_output)?);
+    } else if args.prometheus {
+        print!("{}", prometheus::render(std::slice::from_ref(channel)));
+    } else if args.value_only {
+        println!("{:.2}%", usage);
+    } else if args.summary {
+        match power_mw {
+            Some(power_mw) => println!("Usage: {:>6.2}%  Power: {:>7.1} mW", usage, power_mw),
+            None => println!("Usage: {:>6.2}%", usage),
+        }
+    } else {
+        // Verbose (default)
+        println!("{:>0} / {:<0}", channel.group, channel.subgroup);
+        for state in &channel.states {
+            println!("  {:>6}: {:>21} µs", state.name, state.residency);
+        }
+        println!(
+            "  {:>15}: {:>12} µs (active)",
+            "→ Total active",
+            channel.active_residency()
         );
+        println!(
+            "  {:>15}: {:>12} µs (total)",
+            "→ Total",
+            channel.total_residency()
+        );
+        println!("  {:>15}: {:>12.2} %", "→ Usage", usage);
+        if let Some(power_mw) = power_mw {
+            println!("  {:>15}: {:>12.1} mW", "→ Power", power_mw);
+        }
+        if let Some(freq_mhz) = freq_mhz.filter(|f| !f.is_nan()) {
+            println!("  {:>15}: {:>12.0} MHz", "→ Clock", freq_mhz);
+        }
     }
 
     Ok(())
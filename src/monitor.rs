@@ -0,0 +1,124 @@
+//! Background sampling service for continuous (`--watch`) monitoring.
+
+use crate::ioreport::{Channel, IOReport, IOReportError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often the background thread wakes up to check the stop flag.
+/// Kept short and fixed so shutdown stays responsive regardless of the
+/// user-configured sample interval.
+const TICK: Duration = Duration::from_millis(500);
+
+/// A reading emitted by the background sampling thread, or an error if a
+/// sample could not be captured. The `Duration` is the real wall-clock gap
+/// between the two samples the delta was computed from, which is only ever
+/// *at least* the configured interval (it is rounded up to the next [`TICK`]
+/// boundary), so callers must use it rather than the configured interval
+/// when converting energy counters to average power.
+pub type MonitorResult = Result<(Vec<Channel>, Duration), IOReportError>;
+
+/// Whether enough time has passed since the last sample to take another one.
+///
+/// Pulled out of the sampling loop so the interval-vs-tick decision can be
+/// tested without spinning up a real background thread.
+pub fn should_sample(elapsed_since_last_tick: Duration, interval: Duration) -> bool {
+    elapsed_since_last_tick >= interval
+}
+
+/// Runs `IOReport::get_delta` on a background thread at a fixed interval,
+/// handing each fresh reading back over a channel.
+///
+/// The thread wakes every [`TICK`] to check whether it has been asked to
+/// stop, but only takes a new sample once the configured `interval` has
+/// elapsed, so it stays responsive to shutdown without oversleeping.
+pub struct MonitorService {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MonitorService {
+    /// Spawns the sampling thread and returns the service along with the
+    /// receiving end of the channel that carries each delta.
+    pub fn start(report: IOReport, interval: Duration) -> (Self, Receiver<MonitorResult>) {
+        let (tx, rx): (Sender<MonitorResult>, Receiver<MonitorResult>) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_thread = Arc::clone(&stop);
+
+        let handle = thread::spawn(move || {
+            let mut last_sample = match report.sample() {
+                Ok(sample) => sample,
+                Err(err) => {
+                    let _ = tx.send(Err(err));
+                    return;
+                }
+            };
+            let mut last_sample_time = Instant::now();
+            let mut last_tick = Instant::now();
+
+            while !stop_thread.load(Ordering::Relaxed) {
+                thread::sleep(TICK);
+
+                if stop_thread.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                if !should_sample(last_tick.elapsed(), interval) {
+                    continue;
+                }
+                last_tick = Instant::now();
+
+                let sample = match report.sample() {
+                    Ok(sample) => sample,
+                    Err(err) => {
+                        let _ = tx.send(Err(err));
+                        break;
+                    }
+                };
+
+                let elapsed = last_sample_time.elapsed();
+                let delta = IOReport::get_delta(&last_sample, &sample, elapsed)
+                    .map(|channels| (channels, elapsed));
+                last_sample = sample;
+                last_sample_time = Instant::now();
+
+                if tx.send(delta).is_err() {
+                    // Receiver gone; nothing left to report to.
+                    break;
+                }
+            }
+        });
+
+        (
+            Self {
+                stop,
+                handle: Some(handle),
+            },
+            rx,
+        )
+    }
+
+    /// Signals the background thread to stop after its current tick.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Signals shutdown and blocks until the background thread exits.
+    pub fn join(mut self) {
+        self.stop();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MonitorService {
+    fn drop(&mut self) {
+        self.stop();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Incremental aggregate statistics over a sequence of GPU readings.
+
+use crate::ioreport::GPUChannel;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Running min/max/mean/last over a stream of `f64` samples, updated in
+/// O(1) per sample without retaining the individual readings.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningStat {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u64,
+    last: f64,
+}
+
+impl RunningStat {
+    fn new(first: f64) -> Self {
+        Self {
+            min: first,
+            max: first,
+            sum: first,
+            count: 1,
+            last: first,
+        }
+    }
+
+    fn update(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.count += 1;
+        self.last = value;
+    }
+
+    /// The minimum value observed so far.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// The maximum value observed so far.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// The mean of all observed values.
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+
+    /// The most recently observed value.
+    pub fn last(&self) -> f64 {
+        self.last
+    }
+}
+
+/// Accumulates successive [`GPUChannel`] deltas into rolling statistics:
+/// min/max/mean/last of the active-usage percentage, plus the mean
+/// residency share of each named performance state.
+///
+/// Intended to pair with the `--watch` monitoring mode, where a single
+/// instantaneous reading is less useful than a summary across the run.
+#[derive(Debug, Default, Serialize)]
+pub struct UsageStats {
+    usage: Option<RunningStat>,
+    state_residency: HashMap<String, RunningStat>,
+}
+
+impl UsageStats {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds one more [`GPUChannel`] delta into the running statistics.
+    pub fn record(&mut self, channel: &GPUChannel) {
+        let usage = channel.usage();
+        match &mut self.usage {
+            Some(stat) => stat.update(usage),
+            None => self.usage = Some(RunningStat::new(usage)),
+        }
+
+        let total = channel.total_residency();
+        for state in &channel.states {
+            let share = if total == 0 {
+                0.0
+            } else {
+                (state.residency as f64 / total as f64) * 100.0
+            };
+
+            match self.state_residency.get_mut(&state.name) {
+                Some(stat) => stat.update(share),
+                None => {
+                    self.state_residency
+                        .insert(state.name.clone(), RunningStat::new(share));
+                }
+            }
+        }
+    }
+
+    /// The min/max/mean/last of the active-usage percentage across all
+    /// recorded deltas, or `None` if nothing has been recorded yet.
+    pub fn usage(&self) -> Option<&RunningStat> {
+        self.usage.as_ref()
+    }
+
+    /// The mean residency share (as a percentage of total) for each named
+    /// performance state across all recorded deltas.
+    pub fn state_residency(&self) -> &HashMap<String, RunningStat> {
+        &self.state_residency
+    }
+}
@@ -0,0 +1,40 @@
+// Integration tests for the Prometheus/OpenMetrics output formatter.
+
+use siligpu::format::prometheus;
+use siligpu::ioreport::{GPUChannel, GPUState};
+
+fn channel() -> GPUChannel {
+    GPUChannel {
+        group: "GPU Stats".to_string(),
+        subgroup: "GPU Performance States".to_string(),
+        states: vec![
+            GPUState { name: "IDLE".to_string(), residency: 80, is_active: false },
+            GPUState { name: "P3".to_string(), residency: 20, is_active: true },
+        ],
+    }
+}
+
+#[test]
+fn integration_prometheus_render_contains_expected_metrics() {
+    let output = prometheus::render(&[channel()]);
+
+    assert!(output.contains("# TYPE siligpu_usage_percent gauge"));
+    assert!(output.contains("siligpu_usage_percent{group=\"GPU Stats\",subgroup=\"GPU Performance States\"} 20"));
+    assert!(output.contains("siligpu_active_residency_microseconds{group=\"GPU Stats\",subgroup=\"GPU Performance States\"} 20"));
+    assert!(output.contains("siligpu_total_residency_microseconds{group=\"GPU Stats\",subgroup=\"GPU Performance States\"} 100"));
+    assert!(output.contains("siligpu_state_residency_microseconds{group=\"GPU Stats\",subgroup=\"GPU Performance States\",state=\"P3\"} 20"));
+}
+
+#[test]
+fn integration_prometheus_render_escapes_label_values() {
+    let channel = GPUChannel {
+        group: "GPU \"Stats\"".to_string(),
+        subgroup: "Sub\\group".to_string(),
+        states: vec![],
+    };
+
+    let output = prometheus::render(&[channel]);
+
+    assert!(output.contains("group=\"GPU \\\"Stats\\\"\""));
+    assert!(output.contains("subgroup=\"Sub\\\\group\""));
+}
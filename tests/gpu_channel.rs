@@ -1,7 +1,9 @@
 // Integration tests for the GPU channel logic.
 // Runs as part of `cargo test` and uses the public crate API.
 
-use siligpu::ioreport::{GPUChannel, GPUState};
+use siligpu::dvfs::{DvfsPoint, DvfsTable};
+use siligpu::ioreport::{EnergyChannel, GPUChannel, GPUState};
+use std::time::Duration;
 
 const EPS: f64 = 1e-6;
 
@@ -25,3 +27,59 @@ fn integration_gpu_channel_usage_zero_total() {
     let channel = GPUChannel { group: "Test".to_string(), subgroup: "Test".to_string(), states: vec![] };
     assert!((channel.usage() - 0.0).abs() < EPS, "usage expected 0.0, got {}", channel.usage());
 }
+
+#[test]
+fn integration_energy_channel_average_milliwatts() {
+    let channel = EnergyChannel {
+        group: "Energy Model".to_string(),
+        subgroup: "GPU Energy".to_string(),
+        channel_name: "GPU Energy".to_string(),
+        energy_uj: 2_000_000,
+    };
+
+    // 2,000,000 uJ over 1s = 2W = 2000mW.
+    let power = channel.average_milliwatts(Duration::from_secs(1));
+    assert!((power - 2000.0).abs() < EPS, "power expected ~2000.0, got {}", power);
+}
+
+#[test]
+fn integration_energy_channel_zero_elapsed() {
+    let channel = EnergyChannel {
+        group: "Energy Model".to_string(),
+        subgroup: "GPU Energy".to_string(),
+        channel_name: "GPU Energy".to_string(),
+        energy_uj: 2_000_000,
+    };
+
+    assert_eq!(channel.average_milliwatts(Duration::from_secs(0)), 0.0);
+}
+
+#[test]
+fn integration_effective_frequency_mhz() {
+    let states = vec![
+        GPUState { name: "IDLE".to_string(), residency: 100, is_active: false },
+        GPUState { name: "P1".to_string(), residency: 25, is_active: true },
+        GPUState { name: "P2".to_string(), residency: 75, is_active: true },
+    ];
+    let channel = GPUChannel { group: "Test".to_string(), subgroup: "Test".to_string(), states };
+
+    let dvfs: DvfsTable = vec![
+        DvfsPoint { freq_mhz: 400.0, voltage_mv: 600 },
+        DvfsPoint { freq_mhz: 1200.0, voltage_mv: 900 },
+    ]
+    .into();
+
+    // (25 * 400 + 75 * 1200) / 100 = 1000 MHz
+    let freq = channel.effective_frequency_mhz(&dvfs);
+    assert!((freq - 1000.0).abs() < EPS, "frequency expected ~1000.0, got {}", freq);
+}
+
+#[test]
+fn integration_effective_frequency_mhz_table_mismatch() {
+    let states = vec![GPUState { name: "P1".to_string(), residency: 25, is_active: true }];
+    let channel = GPUChannel { group: "Test".to_string(), subgroup: "Test".to_string(), states };
+
+    let dvfs: DvfsTable = Vec::new().into();
+
+    assert!(channel.effective_frequency_mhz(&dvfs).is_nan());
+}
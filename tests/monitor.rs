@@ -0,0 +1,43 @@
+// Integration tests for the `--watch` sampling-interval decision.
+// `MonitorService` itself spawns a thread around a live `IOReport`, which
+// requires real hardware, but the interval-vs-tick logic is pulled out into
+// `should_sample` specifically so it can be exercised here.
+
+use siligpu::monitor::should_sample;
+use std::time::Duration;
+
+#[test]
+fn integration_should_sample_not_yet_elapsed() {
+    assert!(!should_sample(
+        Duration::from_millis(499),
+        Duration::from_millis(500)
+    ));
+}
+
+#[test]
+fn integration_should_sample_exactly_elapsed() {
+    assert!(should_sample(
+        Duration::from_millis(500),
+        Duration::from_millis(500)
+    ));
+}
+
+#[test]
+fn integration_should_sample_overshoot() {
+    // The tick loop only guarantees *at least* `interval` has passed,
+    // rounded up to the next tick boundary, so overshoot must still sample.
+    assert!(should_sample(
+        Duration::from_millis(900),
+        Duration::from_millis(700)
+    ));
+}
+
+#[test]
+fn integration_should_sample_sub_tick_interval() {
+    // A `--time` shorter than the 500ms tick (e.g. `100ms`) is due as soon
+    // as its own interval has elapsed; the loop then samples on its next tick.
+    assert!(should_sample(
+        Duration::from_millis(100),
+        Duration::from_millis(100)
+    ));
+}
@@ -1,8 +1,8 @@
 // Integration tests for the CLI duration parser.
 // Makes use of the crate's `parse_duration` helper.
 
-use std::time::Duration;
 use siligpu::{parse_duration, ParseDurationError};
+use std::time::Duration;
 
 #[test]
 fn integration_parse_duration_various() {
@@ -22,3 +22,47 @@ fn integration_parse_duration_various() {
     ));
     assert!(matches!(parse_duration(""), Err(ParseDurationError::Empty)));
 }
+
+#[test]
+fn integration_parse_duration_fractional() {
+    assert_eq!(parse_duration("0.5s").unwrap(), Duration::from_millis(500));
+    assert_eq!(parse_duration("2.5m").unwrap(), Duration::from_secs(150));
+}
+
+#[test]
+fn integration_parse_duration_days() {
+    assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86_400));
+}
+
+#[test]
+fn integration_parse_duration_compound() {
+    assert_eq!(parse_duration("1m30s").unwrap(), Duration::from_secs(90));
+    assert_eq!(
+        parse_duration("1h15m").unwrap(),
+        Duration::from_secs(3600 + 15 * 60)
+    );
+    assert_eq!(
+        parse_duration("1H30M15S").unwrap(),
+        Duration::from_secs(3600 + 30 * 60 + 15)
+    );
+}
+
+#[test]
+fn integration_parse_duration_trailing_number_errors() {
+    assert_eq!(
+        parse_duration("1m30"),
+        Err(ParseDurationError::TrailingNumber)
+    );
+}
+
+#[test]
+fn integration_parse_duration_out_of_order_units_errors() {
+    assert_eq!(
+        parse_duration("1s1h"),
+        Err(ParseDurationError::InvalidNumber)
+    );
+    assert_eq!(
+        parse_duration("1s2s"),
+        Err(ParseDurationError::InvalidNumber)
+    );
+}
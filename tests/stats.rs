@@ -0,0 +1,38 @@
+// Integration tests for the rolling usage-statistics accumulator.
+
+use siligpu::ioreport::{GPUChannel, GPUState};
+use siligpu::stats::UsageStats;
+
+const EPS: f64 = 1e-6;
+
+fn channel(active: i64, idle: i64) -> GPUChannel {
+    GPUChannel {
+        group: "Test".to_string(),
+        subgroup: "Test".to_string(),
+        states: vec![
+            GPUState { name: "IDLE".to_string(), residency: idle, is_active: false },
+            GPUState { name: "P1".to_string(), residency: active, is_active: true },
+        ],
+    }
+}
+
+#[test]
+fn integration_usage_stats_min_max_mean_last() {
+    let mut stats = UsageStats::new();
+
+    stats.record(&channel(10, 90)); // 10%
+    stats.record(&channel(50, 50)); // 50%
+    stats.record(&channel(90, 10)); // 90%
+
+    let usage = stats.usage().expect("at least one recorded sample");
+    assert!((usage.min() - 10.0).abs() < EPS);
+    assert!((usage.max() - 90.0).abs() < EPS);
+    assert!((usage.mean() - 50.0).abs() < EPS);
+    assert!((usage.last() - 90.0).abs() < EPS);
+}
+
+#[test]
+fn integration_usage_stats_empty() {
+    let stats = UsageStats::new();
+    assert!(stats.usage().is_none());
+}